@@ -0,0 +1,28 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+/// Where the backend should accept connections from, parsed from a config
+/// string such as `tcp:127.0.0.1:3000` or `unix:/run/fide.sock`.
+#[derive(Debug, Clone)]
+pub enum ListenAddr {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl ListenAddr {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        if let Some(rest) = s.strip_prefix("tcp:") {
+            let addr: SocketAddr = rest
+                .parse()
+                .map_err(|e| format!("invalid tcp address '{}': {}", rest, e))?;
+            Ok(ListenAddr::Tcp(addr))
+        } else if let Some(rest) = s.strip_prefix("unix:") {
+            Ok(ListenAddr::Unix(PathBuf::from(rest)))
+        } else {
+            Err(format!(
+                "unrecognized listen address '{}', expected 'tcp:<addr>' or 'unix:<path>'",
+                s
+            ))
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Json,
-    routing::{get, post},
+    extract::{Host, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json},
+    routing::{delete, get, post},
     Router,
 };
 use serde::{Deserialize, Serialize};
@@ -11,11 +11,14 @@ use tower_http::cors::{Any, CorsLayer};
 use tracing::info;
 
 mod boards;
+mod listen;
 mod projects;
+mod qr;
 mod templates;
 
 use boards::BoardConfig;
-use projects::{CreateProjectRequest, CreateProjectResponse, ProjectManager};
+use listen::ListenAddr;
+use projects::{CreateProjectRequest, CreateProjectResponse, DeleteProjectError, ProjectManager};
 
 #[derive(Clone)]
 struct AppState {
@@ -44,17 +47,57 @@ async fn main() {
     let app = Router::new()
         .route("/api/boards", get(get_boards))
         .route("/api/projects", post(create_project))
-        .route("/api/projects/:id/files/*path", get(get_project_file))
+        .route("/api/projects/:id", delete(delete_project))
+        .route(
+            "/api/projects/:id/files/*path",
+            get(get_project_file).put(write_project_file),
+        )
+        .route("/api/projects/:id/qr", get(get_project_qr))
         .layer(cors)
         .with_state(state);
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
-        .await
-        .unwrap();
+    let listen_addr = ListenAddr::parse(
+        &std::env::var("FIDE_LISTEN").unwrap_or_else(|_| "tcp:127.0.0.1:3000".to_string()),
+    )
+    .expect("invalid FIDE_LISTEN address");
+
+    let reuse = std::env::var("FIDE_LISTEN_REUSE")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(true);
 
-    info!("🚀 FIDE Backend listening on http://localhost:3000");
+    serve(listen_addr, reuse, app).await;
+}
+
+async fn serve(addr: ListenAddr, reuse: bool, app: Router) {
+    match addr {
+        ListenAddr::Tcp(addr) => {
+            let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+            info!("🚀 FIDE Backend listening on http://{}", addr);
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown_signal())
+                .await
+                .unwrap();
+        }
+        ListenAddr::Unix(path) => {
+            if reuse && path.exists() {
+                std::fs::remove_file(&path).unwrap();
+            }
+            let listener = tokio::net::UnixListener::bind(&path).unwrap();
+            info!("🚀 FIDE Backend listening on unix:{}", path.display());
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown_signal())
+                .await
+                .unwrap();
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
 
-    axum::serve(listener, app).await.unwrap();
+async fn shutdown_signal() {
+    tokio::signal::ctrl_c()
+        .await
+        .expect("failed to install Ctrl+C handler");
+    info!("Shutdown signal received, draining in-flight requests");
 }
 
 async fn get_boards() -> Json<Vec<BoardConfig>> {
@@ -84,6 +127,22 @@ async fn create_project(
     }
 }
 
+async fn delete_project(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> StatusCode {
+    info!("DELETE /api/projects/{}", project_id);
+
+    match state.project_manager.delete_project(&project_id).await {
+        Ok(()) => StatusCode::NO_CONTENT,
+        Err(DeleteProjectError::NotFound) => StatusCode::NOT_FOUND,
+        Err(e @ DeleteProjectError::Io(_)) => {
+            tracing::error!("Failed to delete project: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
 async fn get_project_file(
     State(state): State<AppState>,
     Path((project_id, file_path)): Path<(String, String)>,
@@ -105,3 +164,76 @@ async fn get_project_file(
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+struct QrParams {
+    format: Option<String>,
+}
+
+async fn get_project_qr(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(params): Query<QrParams>,
+    Host(host): Host,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, StatusCode> {
+    info!("GET /api/projects/{}/qr", project_id);
+
+    // Derive the base URL from the requesting host so the QR code actually
+    // resolves from the other device it's meant to be scanned on.
+    let scheme = headers
+        .get("x-forwarded-proto")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("http");
+    let base_url = format!("{}://{}", scheme, host);
+
+    let workspace_url = state
+        .project_manager
+        .workspace_url(&project_id, &base_url)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let code = qr::encode(&workspace_url).map_err(|e| {
+        tracing::error!("Failed to encode QR code: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let wants_png = params.format.as_deref() == Some("png")
+        || headers
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.contains("image/png"))
+            .unwrap_or(false);
+
+    if wants_png {
+        let png = qr::render_png(&code).map_err(|e| {
+            tracing::error!("Failed to render QR PNG: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        Ok(([(header::CONTENT_TYPE, "image/png")], png).into_response())
+    } else {
+        Ok(([(header::CONTENT_TYPE, "image/svg+xml")], qr::render_svg(&code)).into_response())
+    }
+}
+
+async fn write_project_file(
+    State(state): State<AppState>,
+    Path((project_id, file_path)): Path<(String, String)>,
+    body: String,
+) -> Result<StatusCode, StatusCode> {
+    info!(
+        "PUT /api/projects/{}/files/{}",
+        project_id, file_path
+    );
+
+    match state
+        .project_manager
+        .write_file_content(&project_id, &file_path, &body)
+        .await
+    {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            tracing::error!("Failed to write file: {}", e);
+            Err(StatusCode::NOT_FOUND)
+        }
+    }
+}
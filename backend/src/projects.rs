@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+use tracing::error;
 use uuid::Uuid;
 
 use crate::boards::get_board_by_id;
-use crate::templates::{FileNode, generate_file_tree, get_template_file_content};
+use crate::templates::{generate_file_tree, materialize_template, FileNode};
+
+const PROJECTS_TREE: &str = "projects";
+const WORKSPACES_DIR: &str = "workspaces";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateProjectRequest {
@@ -20,13 +26,83 @@ pub struct CreateProjectResponse {
     pub workspace_url: String,
 }
 
-#[derive(Debug, Clone)]
+/// Distinguishes "no such project" from "found it, but deleting it failed"
+/// so the HTTP layer can report 404 vs 500 instead of collapsing both to 404.
+#[derive(Debug)]
+pub enum DeleteProjectError {
+    NotFound,
+    Io(String),
+}
+
+impl std::fmt::Display for DeleteProjectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeleteProjectError::NotFound => write!(f, "project not found"),
+            DeleteProjectError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ProjectInfo {
     project_id: String,
     container_id: String,
     project_name: String,
     board_id: String,
     template_path: String,
+    workspace_path: String,
+}
+
+/// Join `file_path` onto `workspace_path`, rejecting anything that could
+/// escape the workspace (`..`, an absolute path, or a Windows prefix) before
+/// it ever reaches the filesystem.
+fn safe_workspace_path(workspace_path: &Path, file_path: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(file_path);
+    let is_safe = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+
+    if !is_safe {
+        return Err(format!("Invalid file path: {}", file_path));
+    }
+
+    Ok(workspace_path.join(relative))
+}
+
+fn db() -> &'static sled::Db {
+    static DB: OnceLock<sled::Db> = OnceLock::new();
+    DB.get_or_init(|| sled::open("data/projects.sled").expect("failed to open project database"))
+}
+
+fn load_persisted_projects() -> HashMap<String, ProjectInfo> {
+    let mut projects = HashMap::new();
+
+    let tree = match db().open_tree(PROJECTS_TREE) {
+        Ok(tree) => tree,
+        Err(e) => {
+            error!("Failed to open project tree: {}", e);
+            return projects;
+        }
+    };
+
+    for entry in tree.iter() {
+        let (key, value) = match entry {
+            Ok(kv) => kv,
+            Err(e) => {
+                error!("Failed to read project entry: {}", e);
+                continue;
+            }
+        };
+
+        match serde_json::from_slice::<ProjectInfo>(&value) {
+            Ok(info) => {
+                projects.insert(String::from_utf8_lossy(&key).to_string(), info);
+            }
+            Err(e) => error!("Failed to deserialize project entry: {}", e),
+        }
+    }
+
+    projects
 }
 
 pub struct ProjectManager {
@@ -36,7 +112,7 @@ pub struct ProjectManager {
 impl ProjectManager {
     pub fn new() -> Self {
         Self {
-            projects: RwLock::new(HashMap::new()),
+            projects: RwLock::new(load_persisted_projects()),
         }
     }
 
@@ -51,18 +127,26 @@ impl ProjectManager {
         let project_id = Uuid::new_v4().to_string();
         let container_id = Uuid::new_v4().to_string();
 
-        // Generate file tree from template
-        let file_tree = generate_file_tree(&board.template_path, project_name)?;
+        let workspace_path = PathBuf::from(WORKSPACES_DIR).join(&project_id);
+        materialize_template(&board.template_path, &workspace_path, project_name)?;
+
+        // Generate file tree from the materialized workspace, not the pristine template.
+        let file_tree = generate_file_tree(&workspace_path.to_string_lossy(), project_name)?;
 
-        // Store project info
         let project_info = ProjectInfo {
             project_id: project_id.clone(),
             container_id: container_id.clone(),
             project_name: project_name.to_string(),
             board_id: board_id.to_string(),
             template_path: board.template_path.clone(),
+            workspace_path: workspace_path.to_string_lossy().to_string(),
         };
 
+        let tree = db().open_tree(PROJECTS_TREE).map_err(|e| e.to_string())?;
+        let encoded = serde_json::to_vec(&project_info).map_err(|e| e.to_string())?;
+        tree.insert(project_id.as_bytes(), encoded)
+            .map_err(|e| e.to_string())?;
+
         self.projects
             .write()
             .unwrap()
@@ -81,11 +165,83 @@ impl ProjectManager {
         project_id: &str,
         file_path: &str,
     ) -> Result<String, String> {
+        let workspace_path = {
+            let projects = self.projects.read().unwrap();
+            let project = projects
+                .get(project_id)
+                .ok_or_else(|| format!("Project not found: {}", project_id))?;
+            PathBuf::from(&project.workspace_path)
+        };
+
+        let full_path = safe_workspace_path(&workspace_path, file_path)?;
+        fs::read_to_string(&full_path)
+            .map_err(|e| format!("Failed to read file {}: {}", full_path.display(), e))
+    }
+
+    pub async fn write_file_content(
+        &self,
+        project_id: &str,
+        file_path: &str,
+        content: &str,
+    ) -> Result<(), String> {
+        let workspace_path = {
+            let projects = self.projects.read().unwrap();
+            let project = projects
+                .get(project_id)
+                .ok_or_else(|| format!("Project not found: {}", project_id))?;
+            PathBuf::from(&project.workspace_path)
+        };
+
+        let full_path = safe_workspace_path(&workspace_path, file_path)?;
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        fs::write(&full_path, content)
+            .map_err(|e| format!("Failed to write file {}: {}", full_path.display(), e))
+    }
+
+    /// Build the absolute workspace URL a QR code should point at, using the
+    /// host the request actually came in on rather than a hardcoded address,
+    /// so it resolves from other devices too.
+    pub fn workspace_url(&self, project_id: &str, base_url: &str) -> Option<String> {
         let projects = self.projects.read().unwrap();
-        let project = projects
+        projects
             .get(project_id)
-            .ok_or_else(|| format!("Project not found: {}", project_id))?;
+            .map(|_| format!("{}/workspace/{}", base_url.trim_end_matches('/'), project_id))
+    }
+
+    pub async fn delete_project(&self, project_id: &str) -> Result<(), DeleteProjectError> {
+        // Remove the on-disk workspace first and leave the metadata in place
+        // until that succeeds, so a failed deletion can be retried instead of
+        // leaving an orphaned workspace with no project pointing at it.
+        let workspace_path = {
+            let projects = self.projects.read().unwrap();
+            let project = projects
+                .get(project_id)
+                .ok_or(DeleteProjectError::NotFound)?;
+            PathBuf::from(&project.workspace_path)
+        };
+
+        if workspace_path.exists() {
+            fs::remove_dir_all(&workspace_path).map_err(|e| {
+                DeleteProjectError::Io(format!(
+                    "Failed to remove workspace {}: {}",
+                    workspace_path.display(),
+                    e
+                ))
+            })?;
+        }
+
+        let tree = db()
+            .open_tree(PROJECTS_TREE)
+            .map_err(|e| DeleteProjectError::Io(e.to_string()))?;
+        tree.remove(project_id.as_bytes())
+            .map_err(|e| DeleteProjectError::Io(e.to_string()))?;
+
+        self.projects.write().unwrap().remove(project_id);
 
-        get_template_file_content(&project.template_path, file_path, &project.project_name)
+        Ok(())
     }
 }
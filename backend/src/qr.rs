@@ -0,0 +1,70 @@
+use qrcode::types::Color;
+use qrcode::QrCode;
+
+const MODULE_SIZE: u32 = 8;
+const QUIET_ZONE_MODULES: u32 = 4;
+
+pub fn encode(data: &str) -> Result<QrCode, String> {
+    QrCode::new(data.as_bytes()).map_err(|e| format!("Failed to encode QR code: {}", e))
+}
+
+/// Render a QR code as an SVG by drawing one `<rect>` per dark module over a
+/// viewBox sized to the module grid plus a quiet-zone border.
+pub fn render_svg(code: &QrCode) -> String {
+    let width = code.width() as u32;
+    let quiet_zone = QUIET_ZONE_MODULES * MODULE_SIZE;
+    let dimension = width * MODULE_SIZE + 2 * quiet_zone;
+
+    let mut rects = String::new();
+    for y in 0..width {
+        for x in 0..width {
+            if code[(x as usize, y as usize)] == Color::Dark {
+                rects.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>",
+                    quiet_zone + x * MODULE_SIZE,
+                    quiet_zone + y * MODULE_SIZE,
+                    MODULE_SIZE,
+                    MODULE_SIZE,
+                ));
+            }
+        }
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {d} {d}\" fill=\"black\">\
+<rect width=\"{d}\" height=\"{d}\" fill=\"white\"/>{rects}</svg>",
+        d = dimension,
+        rects = rects,
+    )
+}
+
+/// Render a QR code into a PNG by drawing the module grid into an image buffer.
+pub fn render_png(code: &QrCode) -> Result<Vec<u8>, String> {
+    let width = code.width() as u32;
+    let quiet_zone = QUIET_ZONE_MODULES * MODULE_SIZE;
+    let dimension = width * MODULE_SIZE + 2 * quiet_zone;
+
+    let mut image = image::GrayImage::from_pixel(dimension, dimension, image::Luma([255u8]));
+    for y in 0..width {
+        for x in 0..width {
+            if code[(x as usize, y as usize)] == Color::Dark {
+                for dy in 0..MODULE_SIZE {
+                    for dx in 0..MODULE_SIZE {
+                        image.put_pixel(
+                            quiet_zone + x * MODULE_SIZE + dx,
+                            quiet_zone + y * MODULE_SIZE + dy,
+                            image::Luma([0u8]),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    let mut bytes = Vec::new();
+    image::DynamicImage::ImageLuma8(image)
+        .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode QR PNG: {}", e))?;
+
+    Ok(bytes)
+}
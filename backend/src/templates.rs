@@ -94,18 +94,51 @@ fn build_tree_recursive(dir: &Path, base_path: &Path) -> Result<Vec<FileNode>, S
     Ok(children)
 }
 
-pub fn get_template_file_content(
+/// Copy a template tree into a materialized workspace directory, applying the
+/// `{{PROJECT_NAME}}` substitution to every text file along the way.
+pub fn materialize_template(
     template_path: &str,
-    file_path: &str,
+    workspace_path: &Path,
     project_name: &str,
-) -> Result<String, String> {
-    let full_path = Path::new(template_path).join(file_path);
+) -> Result<(), String> {
+    let base_path = Path::new(template_path);
 
-    let content = fs::read_to_string(&full_path)
-        .map_err(|e| format!("Failed to read file {}: {}", full_path.display(), e))?;
+    if !base_path.exists() {
+        return Err(format!("Template path does not exist: {}", template_path));
+    }
 
-    // Replace template variables
-    let content = content.replace("{{PROJECT_NAME}}", project_name);
+    fs::create_dir_all(workspace_path)
+        .map_err(|e| format!("Failed to create workspace {}: {}", workspace_path.display(), e))?;
+
+    for entry in WalkDir::new(base_path).min_depth(1) {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        let relative_path = path.strip_prefix(base_path).map_err(|e| e.to_string())?;
+        let dest_path = workspace_path.join(relative_path);
+
+        if path.is_dir() {
+            fs::create_dir_all(&dest_path)
+                .map_err(|e| format!("Failed to create {}: {}", dest_path.display(), e))?;
+        } else {
+            if let Some(parent) = dest_path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+
+            match fs::read_to_string(path) {
+                Ok(content) => {
+                    let content = content.replace("{{PROJECT_NAME}}", project_name);
+                    fs::write(&dest_path, content)
+                        .map_err(|e| format!("Failed to write {}: {}", dest_path.display(), e))?;
+                }
+                Err(_) => {
+                    // Not valid UTF-8 (e.g. a binary asset) - copy verbatim.
+                    fs::copy(path, &dest_path)
+                        .map_err(|e| format!("Failed to copy {}: {}", dest_path.display(), e))?;
+                }
+            }
+        }
+    }
 
-    Ok(content)
+    Ok(())
 }
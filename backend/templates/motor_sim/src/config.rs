@@ -1,7 +1,18 @@
+use std::path::PathBuf;
+
+#[derive(Clone)]
 pub struct Config {
     pub max_speed: f64,        // Maximum RPM
     pub acceleration: f64,     // RPM per second
     pub websocket_port: u16,
+    pub history_port: u16,
+    /// Number of telemetry samples retained for replay-on-connect and the
+    /// `/history` HTTP endpoint. Default is one minute of samples at 100ms.
+    pub history_capacity: usize,
+    /// When both are set, the WebSocket server terminates TLS (wss://)
+    /// instead of serving plaintext.
+    pub tls_cert_path: Option<PathBuf>,
+    pub tls_key_path: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -10,6 +21,10 @@ impl Default for Config {
             max_speed: 3000.0,
             acceleration: 500.0,
             websocket_port: 8084,
+            history_port: 8085,
+            history_capacity: 600,
+            tls_cert_path: None,
+            tls_key_path: None,
         }
     }
 }
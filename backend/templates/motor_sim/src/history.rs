@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::response::Json;
+use axum::routing::get;
+use axum::Router;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::motor::MotorTelemetry;
+
+/// Rolling buffer of recent telemetry samples, shared between the simulation
+/// loop (writer), the WebSocket handler (replay-on-connect) and the HTTP
+/// history API (reader).
+pub type HistoryBuffer = Arc<RwLock<VecDeque<MotorTelemetry>>>;
+
+pub fn new_history_buffer(capacity: usize) -> HistoryBuffer {
+    Arc::new(RwLock::new(VecDeque::with_capacity(capacity)))
+}
+
+pub async fn push_sample(history: &HistoryBuffer, capacity: usize, sample: MotorTelemetry) {
+    let mut buffer = history.write().await;
+    if buffer.len() >= capacity {
+        buffer.pop_front();
+    }
+    buffer.push_back(sample);
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryQuery {
+    n: Option<usize>,
+}
+
+pub async fn start_history_server(
+    port: u16,
+    history: HistoryBuffer,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let app = Router::new()
+        .route("/history", get(get_history))
+        .with_state(history);
+
+    let addr = format!("127.0.0.1:{}", port);
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    info!("History HTTP API listening on http://{}/history", addr);
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+async fn get_history(
+    State(history): State<HistoryBuffer>,
+    Query(params): Query<HistoryQuery>,
+) -> Json<Vec<MotorTelemetry>> {
+    let buffer = history.read().await;
+    let n = params.n.unwrap_or(buffer.len()).min(buffer.len());
+    let samples: Vec<MotorTelemetry> = buffer.iter().rev().take(n).rev().cloned().collect();
+    Json(samples)
+}
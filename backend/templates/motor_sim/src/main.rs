@@ -1,17 +1,23 @@
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc, Mutex};
 use tokio::time::{interval, Duration};
-use tracing::{info, error};
+use tracing::info;
 
 mod motor;
 mod websocket;
 mod config;
 mod dlt_format;
+mod supervisor;
+mod history;
+mod tls;
 
 use motor::MotorSimulator;
-use websocket::start_websocket_server;
+use websocket::{start_websocket_server, MotorCommand};
 use config::Config;
 use dlt_format::format_as_dlt_registers;
+use supervisor::supervise;
+use history::{new_history_buffer, push_sample, start_history_server, HistoryBuffer};
 
 #[tokio::main]
 async fn main() {
@@ -22,42 +28,111 @@ async fn main() {
 
     info!("Starting {{PROJECT_NAME}} - Motor Simulation");
 
-    let config = Config::default();
-    
+    let mut config = Config::default();
+
+    // Mirror the FIDE_LISTEN-style env override used by the backend: set both
+    // SIM_TLS_CERT and SIM_TLS_KEY to enable wss:// without recompiling.
+    if let (Ok(cert), Ok(key)) = (std::env::var("SIM_TLS_CERT"), std::env::var("SIM_TLS_KEY")) {
+        config.tls_cert_path = Some(PathBuf::from(cert));
+        config.tls_key_path = Some(PathBuf::from(key));
+    }
+
+    let history_capacity = config.history_capacity;
+    let history_port = config.history_port;
+    let tls_paths = match (&config.tls_cert_path, &config.tls_key_path) {
+        (Some(cert), Some(key)) => Some((cert.clone(), key.clone())),
+        _ => None,
+    };
+
     // Create broadcast channel for telemetry data
     let (tx, _rx) = broadcast::channel(100);
     let tx = Arc::new(tx);
 
-    // Start WebSocket server
+    // Create command channel so dashboards can drive the simulated motor.
+    // Shared behind a mutex so the simulation loop can pick it back up across restarts.
+    let (cmd_tx, cmd_rx) = mpsc::unbounded_channel::<MotorCommand>();
+    let cmd_rx = Arc::new(Mutex::new(cmd_rx));
+
+    // Rolling buffer of recent telemetry, replayed to newly connected clients
+    let history = new_history_buffer(history_capacity);
+
+    // Start WebSocket server, restarting it with a backoff if it ever errors out
     let ws_tx = tx.clone();
     let ws_port = config.websocket_port;
-    tokio::spawn(async move {
-        if let Err(e) = start_websocket_server(ws_port, ws_tx).await {
-            error!("WebSocket server error: {}", e);
-        }
-    });
+    let ws_cmd_tx = cmd_tx.clone();
+    let ws_history = history.clone();
+    let ws_tls_paths = tls_paths.clone();
+    let ws_handle = tokio::spawn(supervise("websocket server", move || {
+        let tx = ws_tx.clone();
+        let cmd_tx = ws_cmd_tx.clone();
+        let history = ws_history.clone();
+        let tls_paths = ws_tls_paths.clone();
+        async move { start_websocket_server(ws_port, tx, cmd_tx, history, tls_paths).await }
+    }));
 
-    // Create motor simulator
-    let mut motor = MotorSimulator::new(config);
+    // Start the HTTP history API, restarting it the same way
+    let api_history = history.clone();
+    let history_handle = tokio::spawn(supervise("history HTTP API", move || {
+        let history = api_history.clone();
+        async move { start_history_server(history_port, history).await }
+    }));
+
+    // Run the simulation loop under the same supervisor so a panic or error
+    // there restarts it instead of taking the whole process down.
+    let sim_config = config.clone();
+    let sim_tx = tx.clone();
+    let sim_cmd_rx = cmd_rx.clone();
+    let sim_history = history.clone();
+    let sim_handle = tokio::spawn(supervise("simulation loop", move || {
+        let config = sim_config.clone();
+        let tx = sim_tx.clone();
+        let cmd_rx = sim_cmd_rx.clone();
+        let history = sim_history.clone();
+        async move { run_simulation(config, tx, cmd_rx, history, history_capacity).await }
+    }));
+
+    let _ = tokio::join!(ws_handle, history_handle, sim_handle);
+}
 
-    // Main simulation loop
+async fn run_simulation(
+    config: Config,
+    tx: Arc<broadcast::Sender<String>>,
+    cmd_rx: Arc<Mutex<mpsc::UnboundedReceiver<MotorCommand>>>,
+    history: HistoryBuffer,
+    history_capacity: usize,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut motor = MotorSimulator::new(config);
+    let mut cmd_rx = cmd_rx.lock().await;
     let mut tick_interval = interval(Duration::from_millis(100));
-    
+
     loop {
         tick_interval.tick().await;
-        
+
+        // Drain pending control commands before advancing the simulation
+        while let Ok(command) = cmd_rx.try_recv() {
+            match command {
+                MotorCommand::SetTargetSpeed { value } => motor.set_target_speed(value),
+                MotorCommand::SetAcceleration { value } => motor.set_acceleration(value),
+                MotorCommand::EmergencyStop => motor.stop(),
+                MotorCommand::Resume => motor.resume(),
+            }
+        }
+
         // Update motor simulation
         motor.update(0.1); // 100ms = 0.1s
-        
+
         // Get telemetry
         let telemetry = motor.get_telemetry();
-        
+
+        // Buffer for replay-on-connect and the /history HTTP endpoint
+        push_sample(&history, history_capacity, telemetry.clone()).await;
+
         // Log to console
         info!(
             "Motor: speed={:.0} RPM, torque={:.1} Nm, temp={:.1}°C, current={:.2}A",
             telemetry.speed, telemetry.torque, telemetry.temperature, telemetry.current
         );
-        
+
         // Send DLT formatted register messages to WebSocket clients
         let dlt_messages = format_as_dlt_registers(&telemetry);
         for msg in dlt_messages {
@@ -20,6 +20,7 @@ pub struct MotorSimulator {
     current: f64,
     max_speed: f64,
     acceleration: f64,
+    stopped: bool,
 }
 
 impl MotorSimulator {
@@ -32,6 +33,7 @@ impl MotorSimulator {
             current: 0.0,
             max_speed: config.max_speed,
             acceleration: config.acceleration,
+            stopped: false,
         }
     }
 
@@ -60,12 +62,16 @@ impl MotorSimulator {
         let cooling = (self.temperature - 25.0) * 0.1;
         self.temperature += (heat_generation - cooling) * dt;
 
-        // Vary target speed periodically for realistic simulation
-        let time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs_f64();
-        self.target_speed = 1500.0 + 500.0 * (time * 0.2).sin();
+        // Vary target speed periodically for realistic simulation, unless stopped
+        if self.stopped {
+            self.target_speed = 0.0;
+        } else {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs_f64();
+            self.target_speed = 1500.0 + 500.0 * (time * 0.2).sin();
+        }
     }
 
     pub fn get_telemetry(&self) -> MotorTelemetry {
@@ -86,8 +92,22 @@ impl MotorSimulator {
         }
     }
 
-    #[allow(dead_code)]
     pub fn set_target_speed(&mut self, speed: f64) {
         self.target_speed = speed.clamp(0.0, self.max_speed);
     }
+
+    pub fn set_acceleration(&mut self, acceleration: f64) {
+        self.acceleration = acceleration.max(0.0);
+    }
+
+    /// Pin `target_speed` to 0 and suppress the periodic sine-wave retargeting
+    /// until `resume` is called.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+        self.target_speed = 0.0;
+    }
+
+    pub fn resume(&mut self) {
+        self.stopped = false;
+    }
 }
@@ -0,0 +1,29 @@
+use std::future::Future;
+use tokio::time::Duration;
+use tracing::error;
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Run `factory` in a loop, restarting it with a short backoff whenever an
+/// attempt returns an error or panics, instead of letting the task die
+/// silently. Each attempt is spawned on its own task so a panic there can't
+/// take the caller down with it.
+pub async fn supervise<F, Fut>(name: &str, mut factory: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>> + Send + 'static,
+{
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        match tokio::spawn(factory()).await {
+            Ok(Ok(())) => break,
+            Ok(Err(e)) => error!("{} failed: {}, restarting in {:?}", name, e, backoff),
+            Err(join_err) => error!("{} panicked: {}, restarting in {:?}", name, join_err, backoff),
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
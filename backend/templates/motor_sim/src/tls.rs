@@ -0,0 +1,30 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+
+/// Build a `TlsAcceptor` from a PEM certificate chain and private key.
+pub fn load_acceptor(
+    cert_path: &Path,
+    key_path: &Path,
+) -> Result<TlsAcceptor, Box<dyn std::error::Error + Send + Sync>> {
+    let mut cert_reader = BufReader::new(File::open(cert_path)?);
+    let certs = rustls_pemfile::certs(&mut cert_reader).collect::<Result<Vec<_>, _>>()?;
+
+    let mut key_reader = BufReader::new(File::open(key_path)?);
+    let key = rustls_pemfile::private_key(&mut key_reader)?
+        .ok_or("no private key found in key file")?;
+
+    // rustls requires a process-level crypto provider to be installed before
+    // building a ServerConfig; ignore the error if one is already in place.
+    let _ = tokio_rustls::rustls::crypto::ring::default_provider().install_default();
+
+    let config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?;
+
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
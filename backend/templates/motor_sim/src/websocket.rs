@@ -1,24 +1,65 @@
+use std::path::PathBuf;
 use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::{TcpListener, TcpStream};
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
+use tokio_rustls::TlsAcceptor;
 use tokio_tungstenite::accept_async;
 use tokio_tungstenite::tungstenite::Message;
 use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
 use tracing::{info, error, warn};
 
+use crate::dlt_format::format_as_chart_data;
+use crate::history::HistoryBuffer;
+use crate::tls;
+
+/// Control commands a connected dashboard can send over the telemetry socket
+/// to drive the simulated motor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MotorCommand {
+    SetTargetSpeed { value: f64 },
+    SetAcceleration { value: f64 },
+    EmergencyStop,
+    Resume,
+}
+
 pub async fn start_websocket_server(
     port: u16,
     tx: Arc<broadcast::Sender<String>>,
-) -> Result<(), Box<dyn std::error::Error>> {
+    cmd_tx: mpsc::UnboundedSender<MotorCommand>,
+    history: HistoryBuffer,
+    tls_paths: Option<(PathBuf, PathBuf)>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let addr = format!("127.0.0.1:{}", port);
     let listener = TcpListener::bind(&addr).await?;
-    
-    info!("WebSocket server listening on ws://{}", addr);
+
+    let acceptor = match &tls_paths {
+        Some((cert_path, key_path)) => Some(tls::load_acceptor(cert_path, key_path)?),
+        None => None,
+    };
+
+    info!(
+        "WebSocket server listening on {}://{}",
+        if acceptor.is_some() { "wss" } else { "ws" },
+        addr
+    );
 
     while let Ok((stream, peer_addr)) = listener.accept().await {
         info!("New WebSocket connection from: {}", peer_addr);
         let tx = tx.clone();
-        tokio::spawn(handle_connection(stream, tx, peer_addr.to_string()));
+        let cmd_tx = cmd_tx.clone();
+        let history = history.clone();
+        let acceptor = acceptor.clone();
+        tokio::spawn(handle_connection(
+            stream,
+            tx,
+            cmd_tx,
+            history,
+            acceptor,
+            peer_addr.to_string(),
+        ));
     }
 
     Ok(())
@@ -27,8 +68,29 @@ pub async fn start_websocket_server(
 async fn handle_connection(
     stream: TcpStream,
     tx: Arc<broadcast::Sender<String>>,
+    cmd_tx: mpsc::UnboundedSender<MotorCommand>,
+    history: HistoryBuffer,
+    acceptor: Option<TlsAcceptor>,
     peer_addr: String,
 ) {
+    match acceptor {
+        Some(acceptor) => match acceptor.accept(stream).await {
+            Ok(tls_stream) => handle_ws(tls_stream, tx, cmd_tx, history, peer_addr).await,
+            Err(e) => error!("TLS handshake error from {}: {}", peer_addr, e),
+        },
+        None => handle_ws(stream, tx, cmd_tx, history, peer_addr).await,
+    }
+}
+
+async fn handle_ws<S>(
+    stream: S,
+    tx: Arc<broadcast::Sender<String>>,
+    cmd_tx: mpsc::UnboundedSender<MotorCommand>,
+    history: HistoryBuffer,
+    peer_addr: String,
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     let ws_stream = match accept_async(stream).await {
         Ok(ws) => ws,
         Err(e) => {
@@ -38,7 +100,6 @@ async fn handle_connection(
     };
 
     let (mut ws_sender, mut ws_receiver) = ws_stream.split();
-    let mut rx = tx.subscribe();
 
     // Send initial connection message
     let welcome = serde_json::json!({
@@ -49,35 +110,67 @@ async fn handle_connection(
         let _ = ws_sender.send(Message::Text(msg)).await;
     }
 
-    // Spawn task to receive messages from broadcast channel and send to WebSocket
-    let send_task = tokio::spawn(async move {
-        while let Ok(data) = rx.recv().await {
-            if let Err(e) = ws_sender.send(Message::Text(data)).await {
-                warn!("Error sending to WebSocket: {}", e);
-                break;
+    // Replay buffered history right after the welcome frame so charts back-fill instantly
+    {
+        let buffer = history.read().await;
+        for sample in buffer.iter() {
+            for line in format_as_chart_data(sample) {
+                let _ = ws_sender.send(Message::Text(line)).await;
             }
         }
-    });
+    }
 
-    // Handle incoming WebSocket messages (for future control commands)
-    while let Some(msg) = ws_receiver.next().await {
-        match msg {
-            Ok(Message::Text(text)) => {
-                info!("Received from {}: {}", peer_addr, text);
-                // Future: handle control commands here
-            }
-            Ok(Message::Close(_)) => {
-                info!("Client {} disconnected", peer_addr);
-                break;
+    let mut rx = tx.subscribe();
+
+    // Drive both directions from a single task so we can ack commands on the
+    // same socket the broadcast telemetry is sent over.
+    loop {
+        tokio::select! {
+            telemetry = rx.recv() => {
+                match telemetry {
+                    Ok(data) => {
+                        if let Err(e) = ws_sender.send(Message::Text(data)).await {
+                            warn!("Error sending to WebSocket: {}", e);
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Telemetry channel lagged for {}, skipped {} messages", peer_addr, skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        info!("Telemetry channel closed, disconnecting {}", peer_addr);
+                        break;
+                    }
+                }
             }
-            Err(e) => {
-                error!("WebSocket error from {}: {}", peer_addr, e);
-                break;
+            msg = ws_receiver.next() => {
+                match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        info!("Received from {}: {}", peer_addr, text);
+                        match serde_json::from_str::<MotorCommand>(&text) {
+                            Ok(command) => {
+                                let _ = cmd_tx.send(command.clone());
+                                let ack = serde_json::json!({ "type": "ack", "command": command });
+                                if let Ok(msg) = serde_json::to_string(&ack) {
+                                    let _ = ws_sender.send(Message::Text(msg)).await;
+                                }
+                            }
+                            Err(e) => warn!("Invalid command from {}: {}", peer_addr, e),
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => {
+                        info!("Client {} disconnected", peer_addr);
+                        break;
+                    }
+                    Some(Err(e)) => {
+                        error!("WebSocket error from {}: {}", peer_addr, e);
+                        break;
+                    }
+                    _ => {}
+                }
             }
-            _ => {}
         }
     }
 
-    send_task.abort();
     info!("Connection closed: {}", peer_addr);
 }